@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rdkafka::consumer::{CommitMode, Consumer};
+use rdkafka::{Offset, TopicPartitionList};
+use tracing::{debug, error};
+
+use crate::connectors::kafka::{StreamConsumer, TopicPartition};
+
+/// Tunables for how often accumulated offsets are flushed to the broker.
+#[derive(Debug, Clone, Copy)]
+pub struct CommitConfig {
+    /// Flush at least this often, regardless of how many records landed
+    /// in between; `commit_max_records` can trigger a flush sooner.
+    pub commit_interval: Duration,
+    /// Flush immediately once this many records have accumulated since
+    /// the last flush, without waiting for `commit_interval`.
+    pub commit_max_records: usize,
+}
+
+impl Default for CommitConfig {
+    fn default() -> Self {
+        Self {
+            commit_interval: Duration::from_millis(5000),
+            commit_max_records: 10_000,
+        }
+    }
+}
+
+/// Decouples offset commits from per-chunk processing, à la Arroyo's
+/// `CommitOffsets` strategy: the highest successfully-processed offset
+/// per `TopicPartition` is accumulated in a shared map, and a separate
+/// timer flushes the merged `TopicPartitionList` to the broker on a
+/// configurable cadence instead of committing synchronously after every
+/// small batch.
+pub struct CommitAccumulator {
+    consumer: Arc<StreamConsumer>,
+    config: CommitConfig,
+    offsets: Mutex<HashMap<TopicPartition, i64>>,
+    records_since_flush: AtomicUsize,
+}
+
+impl CommitAccumulator {
+    pub fn new(consumer: Arc<StreamConsumer>, config: CommitConfig) -> Arc<Self> {
+        Arc::new(Self {
+            consumer,
+            config,
+            offsets: Mutex::new(HashMap::new()),
+            records_since_flush: AtomicUsize::new(0),
+        })
+    }
+
+    /// Records the highest offset processed so far for `tp`, flushing
+    /// immediately if `commit_max_records` has been reached since the
+    /// last flush.
+    ///
+    /// Callers must only invoke this once a chunk has fully finished
+    /// processing, and in the order chunks actually completed for a
+    /// given partition -- the `max` merge below has no way to tell a
+    /// late-arriving low offset from a genuinely out-of-order one.
+    pub fn accumulate(&self, tp: TopicPartition, offset: i64, record_count: usize) {
+        {
+            let mut offsets = self.offsets.lock().unwrap();
+            merge_offset(&mut offsets, tp, offset);
+        }
+
+        let pending = self
+            .records_since_flush
+            .fetch_add(record_count, Ordering::Relaxed)
+            + record_count;
+
+        if pending >= self.config.commit_max_records {
+            if let Err(e) = self.flush() {
+                error!("Failed to commit offsets after reaching commit_max_records: {e:?}");
+            }
+        }
+    }
+
+    /// Commits the merged `TopicPartitionList` built from every
+    /// partition's accumulated offset. A no-op when nothing is pending.
+    pub fn flush(&self) -> anyhow::Result<()> {
+        let pending = {
+            let mut offsets = self.offsets.lock().unwrap();
+            std::mem::take(&mut *offsets)
+        };
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut tpl = TopicPartitionList::with_capacity(pending.len());
+        for (tp, commit_offset) in to_commit_offsets(&pending) {
+            tpl.add_partition_offset(&tp.topic, tp.partition, Offset::Offset(commit_offset))?;
+        }
+
+        self.consumer.commit(&tpl, CommitMode::Sync)?;
+        self.records_since_flush.store(0, Ordering::Relaxed);
+        debug!("Committed accumulated offsets for {} partitions", pending.len());
+        Ok(())
+    }
+
+    /// Spawns the timer task that periodically flushes accumulated
+    /// offsets on `commit_interval`, independent of per-chunk processing.
+    pub fn spawn_commit_timer(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let accumulator = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(accumulator.config.commit_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = accumulator.flush() {
+                    error!("Failed to commit accumulated offsets on timer tick: {e:?}");
+                }
+            }
+        })
+    }
+}
+
+/// Merges `offset` into `offsets` for `tp`, keeping whichever offset is
+/// higher so an accumulate call for an earlier offset can never regress
+/// a partition's recorded progress.
+fn merge_offset(offsets: &mut HashMap<TopicPartition, i64>, tp: TopicPartition, offset: i64) {
+    offsets
+        .entry(tp)
+        .and_modify(|highest| *highest = (*highest).max(offset))
+        .or_insert(offset);
+}
+
+/// Maps each partition's highest-processed offset to the "next offset to
+/// read" Kafka expects a commit to carry.
+fn to_commit_offsets(pending: &HashMap<TopicPartition, i64>) -> Vec<(TopicPartition, i64)> {
+    pending
+        .iter()
+        .map(|(tp, offset)| (tp.clone(), offset + 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tp(topic: &str, partition: i32) -> TopicPartition {
+        TopicPartition {
+            topic: topic.to_string(),
+            partition,
+        }
+    }
+
+    #[test]
+    fn merge_offset_keeps_the_highest_seen_offset() {
+        let mut offsets = HashMap::new();
+        merge_offset(&mut offsets, tp("events", 0), 10);
+        merge_offset(&mut offsets, tp("events", 0), 25);
+        // A lower offset arriving after a higher one must not regress it.
+        merge_offset(&mut offsets, tp("events", 0), 15);
+
+        assert_eq!(offsets[&tp("events", 0)], 25);
+    }
+
+    #[test]
+    fn merge_offset_tracks_partitions_independently() {
+        let mut offsets = HashMap::new();
+        merge_offset(&mut offsets, tp("events", 0), 10);
+        merge_offset(&mut offsets, tp("events", 1), 99);
+
+        assert_eq!(offsets[&tp("events", 0)], 10);
+        assert_eq!(offsets[&tp("events", 1)], 99);
+    }
+
+    #[test]
+    fn to_commit_offsets_commits_one_past_the_last_processed_offset() {
+        let mut offsets = HashMap::new();
+        merge_offset(&mut offsets, tp("events", 0), 41);
+
+        let commits = to_commit_offsets(&offsets);
+        assert_eq!(commits, vec![(tp("events", 0), 42)]);
+    }
+}
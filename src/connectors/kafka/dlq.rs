@@ -0,0 +1,272 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+
+use crate::connectors::kafka::ConsumerRecord;
+
+/// Thresholds at which a partition is considered too poisoned to keep
+/// draining. Either limit alone is optional; when both are `None` the
+/// policy accepts an unbounded number of invalid records.
+#[derive(Debug, Clone, Copy)]
+pub struct DlqLimit {
+    /// Stop once more than this many records have been routed to the DLQ.
+    pub max_invalid_messages: Option<u64>,
+    /// Stop once the fraction of invalid records over the trailing
+    /// `window` records exceeds this ratio.
+    pub max_invalid_ratio: Option<f64>,
+    /// Size of the trailing window used to compute `max_invalid_ratio`.
+    pub window: usize,
+    /// `max_invalid_ratio` only applies once at least this many records
+    /// have been observed in the window; below that, a couple of bad
+    /// messages at partition startup can't trip the limit before the
+    /// ratio has any statistical meaning.
+    pub min_sample_size: usize,
+}
+
+impl Default for DlqLimit {
+    fn default() -> Self {
+        Self {
+            max_invalid_messages: None,
+            max_invalid_ratio: None,
+            window: 0,
+            min_sample_size: 30,
+        }
+    }
+}
+
+/// Dead-letter-queue strategy for poison records, modeled on Arroyo's DLQ:
+/// payloads that fail deserialization are produced to a dedicated topic,
+/// tagged with enough headers to trace them back to their origin, instead
+/// of aborting the batch or vanishing silently.
+pub struct DlqPolicy {
+    producer: FutureProducer,
+    topic: String,
+    limit: DlqLimit,
+    invalid_total: AtomicU64,
+    window: Mutex<VecDeque<bool>>,
+}
+
+impl DlqPolicy {
+    pub fn new(producer: FutureProducer, topic: String, limit: DlqLimit) -> Self {
+        Self {
+            producer,
+            topic,
+            window: Mutex::new(VecDeque::with_capacity(limit.window.max(1))),
+            limit,
+            invalid_total: AtomicU64::new(0),
+        }
+    }
+
+    /// Produces `record`'s original payload to the DLQ topic, with headers
+    /// carrying the source topic/partition/offset and the error that
+    /// caused it to be rejected. Awaits the send so the caller can rely on
+    /// it having landed before advancing the consumer offset past it.
+    pub async fn send(&self, record: &ConsumerRecord, error: &anyhow::Error) -> anyhow::Result<()> {
+        let headers = OwnedHeaders::new()
+            .insert(Header {
+                key: "source_topic",
+                value: Some(record.topic.as_str()),
+            })
+            .insert(Header {
+                key: "source_partition",
+                value: Some(record.partition.to_string().as_str()),
+            })
+            .insert(Header {
+                key: "source_offset",
+                value: Some(record.offset.to_string().as_str()),
+            })
+            .insert(Header {
+                key: "error",
+                value: Some(error.to_string().as_str()),
+            });
+
+        let payload = record.payload.as_deref().unwrap_or_default();
+        let key = record.key_str();
+
+        let future_record = FutureRecord::to(&self.topic)
+            .payload(payload)
+            .key(key)
+            .headers(headers);
+
+        self.producer
+            .send(future_record, Duration::from_secs(5))
+            .await
+            .map_err(|(e, _)| {
+                anyhow::anyhow!("failed to produce record to DLQ topic {}: {e}", self.topic)
+            })?;
+
+        self.observe(false);
+        Ok(())
+    }
+
+    /// Records a successfully-processed record so the trailing window used
+    /// for `max_invalid_ratio` reflects the true invalid/valid mix, not
+    /// just the failures.
+    pub fn record_success(&self) {
+        self.observe(true);
+    }
+
+    fn observe(&self, success: bool) {
+        if !success {
+            self.invalid_total.fetch_add(1, Ordering::Relaxed);
+        }
+
+        if self.limit.window > 0 {
+            let mut window = self.window.lock().unwrap();
+            if window.len() == self.limit.window {
+                window.pop_front();
+            }
+            window.push_back(success);
+        }
+    }
+
+    /// Returns an error once the configured invalid-message count or
+    /// invalid-ratio threshold has been exceeded, so the worker stops
+    /// instead of looping forever over a mostly-poisoned partition.
+    pub fn check_limit(&self) -> anyhow::Result<()> {
+        let invalid_total = self.invalid_total.load(Ordering::Relaxed);
+
+        if let Some(max) = self.limit.max_invalid_messages {
+            if invalid_total > max {
+                anyhow::bail!(
+                    "DLQ invalid message count {invalid_total} exceeded configured limit {max}"
+                );
+            }
+        }
+
+        if let Some(max_ratio) = self.limit.max_invalid_ratio {
+            let window = self.window.lock().unwrap();
+            if window.len() >= self.limit.min_sample_size {
+                let invalid = window.iter().filter(|success| !**success).count();
+                let ratio = invalid as f64 / window.len() as f64;
+                if ratio > max_ratio {
+                    anyhow::bail!(
+                        "DLQ invalid ratio {ratio:.3} exceeded configured limit {max_ratio:.3} over the trailing {} records",
+                        window.len()
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy_without_producer(limit: DlqLimit) -> DlqPolicyTestHarness {
+        DlqPolicyTestHarness {
+            limit,
+            invalid_total: AtomicU64::new(0),
+            window: Mutex::new(VecDeque::with_capacity(limit.window.max(1))),
+        }
+    }
+
+    /// Mirrors `DlqPolicy`'s limit-tracking fields without requiring a
+    /// live `FutureProducer`, so the pure count/ratio math can be
+    /// exercised without a broker.
+    struct DlqPolicyTestHarness {
+        limit: DlqLimit,
+        invalid_total: AtomicU64,
+        window: Mutex<VecDeque<bool>>,
+    }
+
+    impl DlqPolicyTestHarness {
+        fn observe(&self, success: bool) {
+            if !success {
+                self.invalid_total.fetch_add(1, Ordering::Relaxed);
+            }
+            if self.limit.window > 0 {
+                let mut window = self.window.lock().unwrap();
+                if window.len() == self.limit.window {
+                    window.pop_front();
+                }
+                window.push_back(success);
+            }
+        }
+
+        fn check_limit(&self) -> anyhow::Result<()> {
+            let invalid_total = self.invalid_total.load(Ordering::Relaxed);
+
+            if let Some(max) = self.limit.max_invalid_messages {
+                if invalid_total > max {
+                    anyhow::bail!("invalid message count {invalid_total} exceeded limit {max}");
+                }
+            }
+
+            if let Some(max_ratio) = self.limit.max_invalid_ratio {
+                let window = self.window.lock().unwrap();
+                if window.len() >= self.limit.min_sample_size {
+                    let invalid = window.iter().filter(|success| !**success).count();
+                    let ratio = invalid as f64 / window.len() as f64;
+                    if ratio > max_ratio {
+                        anyhow::bail!("invalid ratio {ratio:.3} exceeded limit {max_ratio:.3}");
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn max_invalid_messages_trips_once_exceeded() {
+        let harness = policy_without_producer(DlqLimit {
+            max_invalid_messages: Some(2),
+            ..Default::default()
+        });
+
+        harness.observe(false);
+        harness.check_limit().expect("count of 1 is within the limit of 2");
+        harness.observe(false);
+        harness.check_limit().expect("count of 2 is within the limit of 2");
+        harness.observe(false);
+        harness
+            .check_limit()
+            .expect_err("count of 3 exceeds the limit of 2");
+    }
+
+    #[test]
+    fn max_invalid_ratio_ignores_small_samples_below_min_sample_size() {
+        let harness = policy_without_producer(DlqLimit {
+            max_invalid_ratio: Some(0.5),
+            window: 1000,
+            min_sample_size: 30,
+        });
+
+        harness.observe(false);
+        harness.observe(false);
+
+        harness
+            .check_limit()
+            .expect("two failures shouldn't trip a 50% ratio before min_sample_size is reached");
+    }
+
+    #[test]
+    fn max_invalid_ratio_trips_once_min_sample_size_is_reached() {
+        let harness = policy_without_producer(DlqLimit {
+            max_invalid_ratio: Some(0.5),
+            window: 40,
+            min_sample_size: 30,
+        });
+
+        for _ in 0..20 {
+            harness.observe(true);
+            harness.observe(false);
+        }
+        harness
+            .check_limit()
+            .expect("a 50% ratio should not exceed a 50% limit");
+
+        harness.observe(false);
+        harness
+            .check_limit()
+            .expect_err("ratio above 50% over a full sample should trip the limit");
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge_vec, HistogramVec,
+    IntCounterVec, IntGaugeVec,
+};
+use rdkafka::consumer::Consumer;
+use rdkafka::{Offset, TopicPartitionList};
+use tracing::warn;
+
+use crate::connectors::kafka::{StreamConsumer, TopicPartition};
+
+/// Prometheus collectors for the Kafka connector, labeled by topic and
+/// partition so operators can size `buffer_size`/`buffer_timeout` and
+/// spot stuck partitions.
+struct KafkaMetrics {
+    records_processed: IntCounterVec,
+    bytes_processed: IntCounterVec,
+    batch_size: HistogramVec,
+    processing_latency: HistogramVec,
+    commit_latency: HistogramVec,
+    dlq_errors: IntCounterVec,
+    consumer_lag: IntGaugeVec,
+}
+
+impl KafkaMetrics {
+    fn new() -> Self {
+        Self {
+            records_processed: register_int_counter_vec!(
+                "parseable_kafka_records_processed_total",
+                "Total records processed per Kafka topic partition",
+                &["topic", "partition"]
+            )
+            .expect("parseable_kafka_records_processed_total is only registered once"),
+            bytes_processed: register_int_counter_vec!(
+                "parseable_kafka_bytes_processed_total",
+                "Total bytes processed per Kafka topic partition",
+                &["topic", "partition"]
+            )
+            .expect("parseable_kafka_bytes_processed_total is only registered once"),
+            batch_size: register_histogram_vec!(
+                "parseable_kafka_batch_size",
+                "Size of batches emerging from chunks_timeout",
+                &["topic", "partition"]
+            )
+            .expect("parseable_kafka_batch_size is only registered once"),
+            processing_latency: register_histogram_vec!(
+                "parseable_kafka_processing_latency_seconds",
+                "End-to-end processing latency per chunk",
+                &["topic", "partition"]
+            )
+            .expect("parseable_kafka_processing_latency_seconds is only registered once"),
+            commit_latency: register_histogram_vec!(
+                "parseable_kafka_commit_latency_seconds",
+                "Offset commit latency",
+                &["topic", "partition"]
+            )
+            .expect("parseable_kafka_commit_latency_seconds is only registered once"),
+            dlq_errors: register_int_counter_vec!(
+                "parseable_kafka_dlq_errors_total",
+                "Records routed to the DLQ or otherwise failed processing, per Kafka topic partition",
+                &["topic", "partition"]
+            )
+            .expect("parseable_kafka_dlq_errors_total is only registered once"),
+            consumer_lag: register_int_gauge_vec!(
+                "parseable_kafka_consumer_lag",
+                "Consumer lag (high watermark - committed offset) per Kafka topic partition",
+                &["topic", "partition"]
+            )
+            .expect("parseable_kafka_consumer_lag is only registered once"),
+        }
+    }
+
+    fn global() -> &'static KafkaMetrics {
+        static METRICS: OnceLock<KafkaMetrics> = OnceLock::new();
+        METRICS.get_or_init(KafkaMetrics::new)
+    }
+}
+
+#[derive(Default)]
+struct PartitionCounters {
+    records: AtomicU64,
+    bytes: AtomicU64,
+    dlq_errors: AtomicU64,
+}
+
+/// Aggregates per-partition counters in memory and emits them to the
+/// Prometheus vectors on a fixed interval, so hot-path increments stay
+/// cheap atomic adds instead of a label lookup per record.
+pub struct MetricsBuffer {
+    counters: Mutex<HashMap<TopicPartition, Arc<PartitionCounters>>>,
+    flush_interval: Duration,
+}
+
+impl MetricsBuffer {
+    pub fn new(flush_interval: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            counters: Mutex::new(HashMap::new()),
+            flush_interval,
+        })
+    }
+
+    fn counters_for(&self, tp: &TopicPartition) -> Arc<PartitionCounters> {
+        self.counters
+            .lock()
+            .unwrap()
+            .entry(tp.clone())
+            .or_insert_with(|| Arc::new(PartitionCounters::default()))
+            .clone()
+    }
+
+    /// Records a processed chunk's size and observes its batch-size and
+    /// processing-latency histograms. Called once per chunk rather than
+    /// per record, so the histogram observation cost is amortized across
+    /// the whole batch.
+    pub fn record_chunk(
+        &self,
+        tp: &TopicPartition,
+        record_count: usize,
+        byte_count: u64,
+        processing_latency: Duration,
+    ) {
+        let counters = self.counters_for(tp);
+        counters
+            .records
+            .fetch_add(record_count as u64, Ordering::Relaxed);
+        counters.bytes.fetch_add(byte_count, Ordering::Relaxed);
+
+        let partition_label = tp.partition.to_string();
+        let labels = [tp.topic.as_str(), partition_label.as_str()];
+        let metrics = KafkaMetrics::global();
+        metrics
+            .batch_size
+            .with_label_values(&labels)
+            .observe(record_count as f64);
+        metrics
+            .processing_latency
+            .with_label_values(&labels)
+            .observe(processing_latency.as_secs_f64());
+    }
+
+    pub fn record_dlq_error(&self, tp: &TopicPartition) {
+        self.counters_for(tp)
+            .dlq_errors
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn observe_commit_latency(&self, tp: &TopicPartition, latency: Duration) {
+        let partition_label = tp.partition.to_string();
+        KafkaMetrics::global()
+            .commit_latency
+            .with_label_values(&[tp.topic.as_str(), partition_label.as_str()])
+            .observe(latency.as_secs_f64());
+    }
+
+    /// Spawns the timer task that drains the hot-path counters into the
+    /// Prometheus vectors and refreshes consumer lag by querying the
+    /// consumer's watermarks, on `flush_interval`.
+    pub fn spawn_flush_timer(
+        self: &Arc<Self>,
+        consumer: Arc<StreamConsumer>,
+    ) -> tokio::task::JoinHandle<()> {
+        let buffer = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(buffer.flush_interval);
+            loop {
+                interval.tick().await;
+                buffer.flush(&consumer).await;
+            }
+        })
+    }
+
+    /// Drains the hot-path counters into the Prometheus vectors and
+    /// refreshes consumer lag. The watermark/committed-offset lookups
+    /// are blocking rdkafka calls, so each is run via `spawn_blocking`
+    /// rather than directly on this task's async worker thread.
+    async fn flush(&self, consumer: &Arc<StreamConsumer>) {
+        let snapshot: Vec<(TopicPartition, Arc<PartitionCounters>)> = {
+            let counters = self.counters.lock().unwrap();
+            counters.iter().map(|(tp, c)| (tp.clone(), c.clone())).collect()
+        };
+
+        let metrics = KafkaMetrics::global();
+        for (tp, counters) in snapshot {
+            let records = counters.records.swap(0, Ordering::Relaxed);
+            let bytes = counters.bytes.swap(0, Ordering::Relaxed);
+            let dlq_errors = counters.dlq_errors.swap(0, Ordering::Relaxed);
+
+            let partition_label = tp.partition.to_string();
+            let labels = [tp.topic.as_str(), partition_label.as_str()];
+
+            if records > 0 {
+                metrics.records_processed.with_label_values(&labels).inc_by(records);
+            }
+            if bytes > 0 {
+                metrics.bytes_processed.with_label_values(&labels).inc_by(bytes);
+            }
+            if dlq_errors > 0 {
+                metrics.dlq_errors.with_label_values(&labels).inc_by(dlq_errors);
+            }
+
+            let consumer = Arc::clone(consumer);
+            let lag_tp = tp.clone();
+            let lag = tokio::task::spawn_blocking(move || fetch_consumer_lag(&consumer, &lag_tp)).await;
+            match lag {
+                Ok(Some(lag)) => {
+                    metrics.consumer_lag.with_label_values(&labels).set(lag);
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Consumer lag task for {:?} panicked: {e:?}", tp),
+            }
+        }
+    }
+}
+
+/// Computes (high watermark - committed offset) for `tp` by querying the
+/// broker directly. Blocking: must be run via `spawn_blocking`, never
+/// called directly from an async context.
+fn fetch_consumer_lag(consumer: &StreamConsumer, tp: &TopicPartition) -> Option<i64> {
+    let high_watermark = match consumer.fetch_watermarks(&tp.topic, tp.partition, Duration::from_secs(2)) {
+        Ok((_low, high)) => high,
+        Err(e) => {
+            warn!("Failed to fetch watermarks for {:?}: {e:?}", tp);
+            return None;
+        }
+    };
+
+    let mut query = TopicPartitionList::new();
+    if let Err(e) = query.add_partition_offset(&tp.topic, tp.partition, Offset::Invalid) {
+        warn!("Failed to build committed-offsets query for {:?}: {e:?}", tp);
+        return None;
+    }
+
+    let committed = match consumer.committed_offsets(query, Duration::from_secs(2)) {
+        Ok(committed) => committed,
+        Err(e) => {
+            warn!("Failed to fetch committed offsets for {:?}: {e:?}", tp);
+            return None;
+        }
+    };
+
+    let committed_offset = committed
+        .elements()
+        .iter()
+        .find(|e| e.partition() == tp.partition)
+        .and_then(|e| match e.offset() {
+            Offset::Offset(offset) => Some(offset),
+            _ => None,
+        })?;
+
+    Some((high_watermark - committed_offset).max(0))
+}
@@ -0,0 +1,63 @@
+pub mod commit;
+pub mod decoder;
+pub mod dlq;
+pub mod metrics;
+pub mod processor;
+pub mod sink;
+pub mod timestamp;
+
+use bytes::Bytes;
+use rdkafka::message::{BorrowedMessage, Message};
+
+use crate::connectors::kafka::timestamp::TimestampType;
+
+/// Identifies a single Kafka partition within a topic; used as the
+/// accumulation key for per-partition offset commits and metrics.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TopicPartition {
+    pub topic: String,
+    pub partition: i32,
+}
+
+pub use rdkafka::consumer::StreamConsumer;
+
+/// An owned, detached copy of a Kafka record: unlike `BorrowedMessage`,
+/// which borrows from the `StreamConsumer` that produced it, this can be
+/// buffered, chunked by `chunks_timeout`, and moved across the channel
+/// that hands records off to `StreamWorker`.
+#[derive(Debug, Clone)]
+pub struct ConsumerRecord {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
+    pub key: Option<Bytes>,
+    pub payload: Option<Bytes>,
+    /// Raw millisecond-epoch timestamp the broker attached to the
+    /// message, if any. See `timestamp_type` for whether this is a
+    /// producer- or broker-stamped time.
+    pub timestamp: Option<i64>,
+    pub timestamp_type: TimestampType,
+}
+
+impl ConsumerRecord {
+    pub fn key_str(&self) -> &str {
+        self.key
+            .as_deref()
+            .and_then(|key| std::str::from_utf8(key).ok())
+            .unwrap_or_default()
+    }
+}
+
+impl From<&BorrowedMessage<'_>> for ConsumerRecord {
+    fn from(message: &BorrowedMessage<'_>) -> Self {
+        Self {
+            topic: message.topic().to_string(),
+            partition: message.partition(),
+            offset: message.offset(),
+            key: message.key().map(Bytes::copy_from_slice),
+            payload: message.payload().map(Bytes::copy_from_slice),
+            timestamp: message.timestamp().to_millis(),
+            timestamp_type: TimestampType::from(message.timestamp()),
+        }
+    }
+}
@@ -1,4 +1,9 @@
 use crate::connectors::common::processor::Processor;
+use crate::connectors::kafka::commit::CommitAccumulator;
+use crate::connectors::kafka::decoder::PayloadDecoder;
+use crate::connectors::kafka::dlq::DlqPolicy;
+use crate::connectors::kafka::metrics::MetricsBuffer;
+use crate::connectors::kafka::timestamp::EventTimeMode;
 use crate::connectors::kafka::{ConsumerRecord, StreamConsumer, TopicPartition};
 use crate::event::format;
 use crate::event::format::EventFormat;
@@ -8,7 +13,6 @@ use crate::storage::StreamType;
 use async_trait::async_trait;
 use chrono::Utc;
 use futures_util::StreamExt;
-use rdkafka::consumer::{CommitMode, Consumer};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -17,10 +21,37 @@ use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, warn};
 use tracing::{error, info};
 
-#[derive(Default, Debug, Clone)]
-pub struct ParseableSinkProcessor;
+#[derive(Default, Clone)]
+pub struct ParseableSinkProcessor {
+    /// Where poison records go instead of aborting the batch; `None`
+    /// preserves the old fail-fast behavior.
+    pub dlq_policy: Option<Arc<DlqPolicy>>,
+    /// Whether to stamp events with the producer's event time or with
+    /// wall-clock ingest time.
+    pub event_time_mode: EventTimeMode,
+    /// How to turn a raw Kafka payload into JSON before it's converted
+    /// into the Arrow schema used by `into_recordbatch`.
+    pub decoder: PayloadDecoder,
+    /// Where DLQ/error counts are reported; `None` disables metrics
+    /// collection for this processor.
+    pub metrics: Option<Arc<MetricsBuffer>>,
+}
 
 impl ParseableSinkProcessor {
+    pub fn new(
+        dlq_policy: Option<Arc<DlqPolicy>>,
+        event_time_mode: EventTimeMode,
+        decoder: PayloadDecoder,
+        metrics: Option<Arc<MetricsBuffer>>,
+    ) -> Self {
+        Self {
+            dlq_policy,
+            event_time_mode,
+            decoder,
+            metrics,
+        }
+    }
+
     async fn deserialize(
         &self,
         consumer_record: &ConsumerRecord,
@@ -40,7 +71,7 @@ impl ParseableSinkProcessor {
                 Ok(None)
             }
             Some(payload) => {
-                let data: Value = serde_json::from_slice(payload.as_ref())?;
+                let data: Value = self.decoder.decode(payload).await?;
 
                 let event = format::json::Event {
                     data,
@@ -51,13 +82,20 @@ impl ParseableSinkProcessor {
                 // TODO: Implement a buffer (e.g., a wrapper around [Box<dyn ArrayBuilder>]) to optimize the creation of ParseableEvent by compacting the internal RecordBatch.
                 let (record_batch, is_first) = event.into_recordbatch(&schema, None, None)?;
 
+                let parsed_timestamp = match self.event_time_mode {
+                    EventTimeMode::IngestTime => Utc::now().naive_utc(),
+                    EventTimeMode::ProducerEventTime => {
+                        consumer_record.event_time().unwrap_or_else(|| Utc::now().naive_utc())
+                    }
+                };
+
                 let p_event = crate::event::Event {
                     rb: record_batch,
                     stream_name: stream_name.to_string(),
                     origin_format: "json",
                     origin_size: payload.len() as u64,
                     is_first_event: is_first,
-                    parsed_timestamp: Utc::now().naive_utc(),
+                    parsed_timestamp,
                     time_partition: None,
                     custom_partition_values: HashMap::new(),
                     stream_type: StreamType::UserDefined,
@@ -76,8 +114,36 @@ impl Processor<Vec<ConsumerRecord>, ()> for ParseableSinkProcessor {
         debug!("Processing {} records", len);
 
         for cr in records {
-            if let Some(event) = self.deserialize(&cr).await? {
-                event.process().await?;
+            match self.deserialize(&cr).await {
+                Ok(Some(event)) => {
+                    event.process().await?;
+                    if let Some(dlq) = &self.dlq_policy {
+                        dlq.record_success();
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.record_dlq_error(&TopicPartition {
+                            topic: cr.topic.clone(),
+                            partition: cr.partition,
+                        });
+                    }
+
+                    match &self.dlq_policy {
+                        Some(dlq) => {
+                            warn!(
+                                "Routing poison record {}:{}:{} to DLQ: {e:?}",
+                                cr.topic, cr.partition, cr.offset
+                            );
+                            // Awaited before moving to the next record so offsets
+                            // only commit past N once the DLQ send for N lands.
+                            dlq.send(&cr, &e).await?;
+                            dlq.check_limit()?;
+                        }
+                        None => return Err(e),
+                    }
+                }
             }
         }
 
@@ -95,6 +161,8 @@ where
     consumer: Arc<StreamConsumer>,
     buffer_size: usize,
     buffer_timeout: Duration,
+    commit_accumulator: Arc<CommitAccumulator>,
+    metrics: Arc<MetricsBuffer>,
 }
 
 impl<P> StreamWorker<P>
@@ -106,12 +174,28 @@ where
         consumer: Arc<StreamConsumer>,
         buffer_size: usize,
         buffer_timeout: Duration,
+        commit_accumulator: Arc<CommitAccumulator>,
+        metrics: Arc<MetricsBuffer>,
     ) -> Self {
+        // Both the commit accumulator and the metrics buffer are shared,
+        // cross-partition state, so their timers are spawned once here
+        // rather than per partition in `process_partition` -- otherwise
+        // every partition clones this worker and spawns its own
+        // duplicate timer, multiplying the intended commit/flush cadence
+        // by the partition count. The accumulator commits with
+        // `CommitMode::Sync` because of the librdkafka async-commit race:
+        //@see https://github.com/confluentinc/librdkafka/issues/4534
+        //@see https://github.com/confluentinc/librdkafka/issues/4059
+        commit_accumulator.spawn_commit_timer();
+        metrics.spawn_flush_timer(Arc::clone(&consumer));
+
         Self {
             processor,
             consumer,
             buffer_size,
             buffer_timeout,
+            commit_accumulator,
+            metrics,
         }
     }
 
@@ -121,30 +205,49 @@ where
         record_stream: ReceiverStream<ConsumerRecord>,
     ) -> anyhow::Result<()> {
         info!("Started processing stream for {:?}", tp);
-        let chunked_stream = tokio_stream::StreamExt::chunks_timeout(
+        let mut chunked_stream = tokio_stream::StreamExt::chunks_timeout(
             record_stream,
             self.buffer_size,
             self.buffer_timeout,
         );
 
-        chunked_stream
-            .for_each_concurrent(None, |records| async {
-                if let Some(last_record) = records.iter().max_by_key(|r| r.offset) {
-                    let tpl = last_record.offset_to_commit().unwrap();
+        // Chunks are processed one at a time, in order: once `process`
+        // returns an error (e.g. the DLQ's invalid-message/ratio limit
+        // was exceeded) the partition stops and the error propagates to
+        // the caller, instead of logging and letting the stream keep
+        // draining a permanently-poisoned partition forever.
+        while let Some(records) = chunked_stream.next().await {
+            let Some(last_record) = records.iter().max_by_key(|r| r.offset) else {
+                continue;
+            };
+            let offset = last_record.offset;
+            let record_count = records.len();
+            let byte_count: u64 = records
+                .iter()
+                .map(|r| r.payload.as_ref().map(|p| p.len()).unwrap_or_default() as u64)
+                .sum();
 
-                    if let Err(e) = self.processor.process(records).await {
-                        error!("Failed to process records for {:?}: {:?}", tp, e);
-                    }
+            let started_at = std::time::Instant::now();
+            if let Err(e) = self.processor.process(records).await {
+                error!(
+                    "Stopping partition {:?} after unrecoverable processing error: {:?}",
+                    tp, e
+                );
+                return Err(e);
+            }
+            self.metrics
+                .record_chunk(&tp, record_count, byte_count, started_at.elapsed());
 
-                    //CommitMode::Async race condition.
-                    //@see https://github.com/confluentinc/librdkafka/issues/4534
-                    //@see https://github.com/confluentinc/librdkafka/issues/4059
-                    if let Err(e) = self.consumer.commit(&tpl, CommitMode::Sync) {
-                        error!("Failed to commit offsets for {:?}: {:?}", tp, e);
-                    }
-                }
-            })
-            .await;
+            self.commit_accumulator
+                .accumulate(tp.clone(), offset, record_count);
+        }
+
+        let commit_started_at = std::time::Instant::now();
+        if let Err(e) = self.commit_accumulator.flush() {
+            error!("Failed to flush final accumulated offsets for {:?}: {:?}", tp, e);
+        }
+        self.metrics
+            .observe_commit_latency(&tp, commit_started_at.elapsed());
 
         info!("Finished processing stream for {:?}", tp);
         self.processor.post_stream().await?;
@@ -0,0 +1,252 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use arrow_array::RecordBatch;
+use arrow_json::writer::record_batches_to_json_rows;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::ClientConfig;
+use serde_json::{Map, Value};
+use tokio::sync::OnceCell;
+
+use crate::connectors::kafka::decoder::SchemaRegistryClient;
+
+/// How outbound RecordBatches are encoded before being produced.
+#[derive(Debug, Clone)]
+pub enum SinkEncoding {
+    /// One compact JSON object per row, each row produced as its own
+    /// Kafka message (no trailing newline).
+    JsonLines,
+    /// Confluent-wire-format Avro: magic byte + 4-byte schema ID + Avro
+    /// binary, registered (once) under `subject` against the registry.
+    Avro {
+        schema_registry_url: String,
+        subject: String,
+        schema: String,
+    },
+}
+
+/// Configuration for a `KafkaSink`.
+#[derive(Debug, Clone)]
+pub struct KafkaSinkConfig {
+    pub bootstrap_servers: String,
+    pub topic: String,
+    pub encoding: SinkEncoding,
+    /// Column whose value becomes the Kafka message key, for partition
+    /// affinity. `None` lets the producer assign a partition.
+    pub key_column: Option<String>,
+    /// Transactional id; required so production is idempotent and
+    /// transactional.
+    pub transactional_id: String,
+}
+
+/// The reverse of `ParseableSinkProcessor`: produces Arrow RecordBatches
+/// (from a saved query, alert trigger, or stream tail) out to a Kafka
+/// topic. Each flush begins a transaction, sends every row, and commits,
+/// so a downstream consumer reading with `isolation.level=read_committed`
+/// only ever observes whole, exactly-once batches rather than a partial
+/// flush.
+pub struct KafkaSink {
+    producer: FutureProducer,
+    topic: String,
+    encoding: SinkEncoding,
+    key_column: Option<String>,
+    registry: Option<SchemaRegistryClient>,
+    avro_schema_id: OnceCell<u32>,
+    avro_schema: OnceCell<Arc<apache_avro::Schema>>,
+}
+
+impl KafkaSink {
+    pub fn new(config: KafkaSinkConfig) -> anyhow::Result<Self> {
+        let producer: FutureProducer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("transactional.id", &config.transactional_id)
+            .set("enable.idempotence", "true")
+            .create()
+            .context("failed to create Kafka sink producer")?;
+
+        producer
+            .init_transactions(Duration::from_secs(10))
+            .context("failed to initialize Kafka sink transactions")?;
+
+        let registry = match &config.encoding {
+            SinkEncoding::Avro {
+                schema_registry_url,
+                ..
+            } => Some(SchemaRegistryClient::new(schema_registry_url.clone())),
+            SinkEncoding::JsonLines => None,
+        };
+
+        Ok(Self {
+            producer,
+            topic: config.topic,
+            encoding: config.encoding,
+            key_column: config.key_column,
+            registry,
+            avro_schema_id: OnceCell::new(),
+            avro_schema: OnceCell::new(),
+        })
+    }
+
+    /// Encodes and produces every row of `batch` inside a single
+    /// transaction, committing only once every send has been
+    /// acknowledged. Aborts the transaction on the first failure so
+    /// downstream consumers never see a partially-produced batch.
+    pub async fn write(&self, batch: &RecordBatch) -> anyhow::Result<()> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        let rows = record_batches_to_json_rows(&[batch])
+            .context("failed to convert RecordBatch to JSON rows for the Kafka sink")?;
+
+        self.producer
+            .begin_transaction()
+            .context("failed to begin Kafka sink transaction")?;
+
+        if let Err(e) = self.send_rows(&rows).await {
+            self.producer
+                .abort_transaction(Duration::from_secs(10))
+                .context("failed to abort Kafka sink transaction after a send error")?;
+            return Err(e);
+        }
+
+        self.producer
+            .commit_transaction(Duration::from_secs(10))
+            .context("failed to commit Kafka sink transaction")?;
+
+        Ok(())
+    }
+
+    async fn send_rows(&self, rows: &[Map<String, Value>]) -> anyhow::Result<()> {
+        let mut sends = Vec::with_capacity(rows.len());
+        for row in rows {
+            let key = self.key_for(row);
+            let payload = self.encode(row).await?;
+            let topic = self.topic.clone();
+            let producer = self.producer.clone();
+
+            sends.push(async move {
+                let mut record = FutureRecord::to(&topic).payload(&payload);
+                if let Some(key) = key.as_deref() {
+                    record = record.key(key);
+                }
+
+                producer
+                    .send(record, Duration::from_secs(10))
+                    .await
+                    .map_err(|(e, _)| anyhow::anyhow!("failed to produce to sink topic {topic}: {e}"))?;
+
+                Ok::<(), anyhow::Error>(())
+            });
+        }
+
+        futures_util::future::try_join_all(sends).await?;
+        Ok(())
+    }
+
+    fn key_for(&self, row: &Map<String, Value>) -> Option<String> {
+        key_for(self.key_column.as_deref(), row)
+    }
+
+    async fn encode(&self, row: &Map<String, Value>) -> anyhow::Result<Vec<u8>> {
+        match &self.encoding {
+            SinkEncoding::JsonLines => encode_json_lines(row),
+            SinkEncoding::Avro {
+                subject, schema, ..
+            } => self.encode_avro(subject, schema, row).await,
+        }
+    }
+
+    async fn encode_avro(
+        &self,
+        subject: &str,
+        schema: &str,
+        row: &Map<String, Value>,
+    ) -> anyhow::Result<Vec<u8>> {
+        let registry = self
+            .registry
+            .as_ref()
+            .expect("Avro encoding always configures a schema registry client");
+
+        let schema_id = *self
+            .avro_schema_id
+            .get_or_try_init(|| registry.register_avro_schema(subject, schema))
+            .await?;
+
+        let avro_schema = self
+            .avro_schema
+            .get_or_try_init(|| async { apache_avro::Schema::parse_str(schema).map(Arc::new) })
+            .await?;
+        let avro_value = apache_avro::to_value(Value::Object(row.clone()))
+            .context("failed to convert sink row to an Avro value")?;
+        let body = apache_avro::to_avro_datum(avro_schema, avro_value)
+            .context("failed to encode sink row as Avro")?;
+
+        let mut framed = Vec::with_capacity(5 + body.len());
+        framed.push(0x00);
+        framed.extend_from_slice(&schema_id.to_be_bytes());
+        framed.extend_from_slice(&body);
+        Ok(framed)
+    }
+}
+
+/// Picks the Kafka message key out of `row`'s `key_column`, if configured.
+/// Non-string values are stringified rather than dropped, so a numeric or
+/// boolean key column still gets partition affinity.
+fn key_for(key_column: Option<&str>, row: &Map<String, Value>) -> Option<String> {
+    let column = key_column?;
+    row.get(column).map(|value| match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    })
+}
+
+fn encode_json_lines(row: &Map<String, Value>) -> anyhow::Result<Vec<u8>> {
+    serde_json::to_vec(row).context("failed to encode sink row as JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_with(pairs: &[(&str, Value)]) -> Map<String, Value> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect()
+    }
+
+    #[test]
+    fn key_for_returns_none_without_a_configured_key_column() {
+        let row = row_with(&[("id", Value::String("abc".to_string()))]);
+        assert_eq!(key_for(None, &row), None);
+    }
+
+    #[test]
+    fn key_for_returns_none_when_the_column_is_missing_from_the_row() {
+        let row = row_with(&[("id", Value::String("abc".to_string()))]);
+        assert_eq!(key_for(Some("missing"), &row), None);
+    }
+
+    #[test]
+    fn key_for_returns_the_string_value_unquoted() {
+        let row = row_with(&[("id", Value::String("abc".to_string()))]);
+        assert_eq!(key_for(Some("id"), &row), Some("abc".to_string()));
+    }
+
+    #[test]
+    fn key_for_stringifies_non_string_values() {
+        let row = row_with(&[("id", Value::from(42))]);
+        assert_eq!(key_for(Some("id"), &row), Some("42".to_string()));
+    }
+
+    #[test]
+    fn encode_json_lines_produces_compact_json_for_the_row() {
+        let row = row_with(&[("id", Value::from(1)), ("name", Value::String("a".to_string()))]);
+
+        let encoded = encode_json_lines(&row).unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<Value>(&encoded).unwrap(),
+            serde_json::json!({"id": 1, "name": "a"})
+        );
+    }
+}
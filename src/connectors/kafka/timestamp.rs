@@ -0,0 +1,106 @@
+use chrono::NaiveDateTime;
+
+use crate::connectors::kafka::ConsumerRecord;
+
+/// Mirrors Kafka's on-wire distinction between a timestamp stamped by the
+/// producer when the record was created and one stamped by the broker
+/// when it was appended to the log. Only `CreateTime` reflects when the
+/// event actually happened; `LogAppendTime` is itself an ingest-time
+/// stamp and shouldn't be treated as event time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampType {
+    NotAvailable,
+    CreateTime,
+    LogAppendTime,
+}
+
+impl From<rdkafka::Timestamp> for TimestampType {
+    fn from(timestamp: rdkafka::Timestamp) -> Self {
+        match timestamp {
+            rdkafka::Timestamp::NotAvailable => TimestampType::NotAvailable,
+            rdkafka::Timestamp::CreateTime(_) => TimestampType::CreateTime,
+            rdkafka::Timestamp::LogAppendTime(_) => TimestampType::LogAppendTime,
+        }
+    }
+}
+
+/// Per-connector choice of how ingested events are timestamped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventTimeMode {
+    /// Use the broker-supplied `CreateTime` as `parsed_timestamp`, falling
+    /// back to ingest time only when the record carries no usable
+    /// timestamp. Correct for backfills and replays, where messages
+    /// produced hours ago shouldn't all land in the current minute.
+    #[default]
+    ProducerEventTime,
+    /// Always stamp with the wall-clock time the record was ingested,
+    /// matching the connector's original behavior.
+    IngestTime,
+}
+
+impl ConsumerRecord {
+    /// Returns the producer-supplied event time, if the broker attached
+    /// one and it is a `CreateTime` rather than a `LogAppendTime` stamp.
+    pub fn event_time(&self) -> Option<NaiveDateTime> {
+        if self.timestamp_type != TimestampType::CreateTime {
+            return None;
+        }
+
+        chrono::DateTime::from_timestamp_millis(self.timestamp?).map(|dt| dt.naive_utc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_with(timestamp: Option<i64>, timestamp_type: TimestampType) -> ConsumerRecord {
+        ConsumerRecord {
+            topic: "events".to_string(),
+            partition: 0,
+            offset: 0,
+            key: None,
+            payload: None,
+            timestamp,
+            timestamp_type,
+        }
+    }
+
+    #[test]
+    fn event_time_reads_create_time() {
+        let record = record_with(Some(1_700_000_000_000), TimestampType::CreateTime);
+
+        let event_time = record.event_time().expect("CreateTime should produce an event time");
+        assert_eq!(event_time.and_utc().timestamp_millis(), 1_700_000_000_000);
+    }
+
+    #[test]
+    fn event_time_ignores_log_append_time() {
+        let record = record_with(Some(1_700_000_000_000), TimestampType::LogAppendTime);
+
+        assert_eq!(record.event_time(), None);
+    }
+
+    #[test]
+    fn event_time_is_none_when_timestamp_not_available() {
+        let record = record_with(None, TimestampType::NotAvailable);
+
+        assert_eq!(record.event_time(), None);
+    }
+
+    #[test]
+    fn timestamp_type_converts_from_rdkafka_timestamp() {
+        assert_eq!(
+            TimestampType::from(rdkafka::Timestamp::CreateTime(1)),
+            TimestampType::CreateTime
+        );
+        assert_eq!(
+            TimestampType::from(rdkafka::Timestamp::LogAppendTime(1)),
+            TimestampType::LogAppendTime
+        );
+        assert_eq!(
+            TimestampType::from(rdkafka::Timestamp::NotAvailable),
+            TimestampType::NotAvailable
+        );
+    }
+}